@@ -1,16 +1,22 @@
 use std::{
     fs::OpenOptions,
-    io::Write,
-    mem::size_of,
-    os::unix::fs::OpenOptionsExt,
+    io::{Read, Seek, SeekFrom, Write},
+    mem::{size_of, ManuallyDrop},
+    ops::{Deref, DerefMut},
+    os::unix::{
+        fs::{FileExt, MetadataExt, OpenOptionsExt},
+        io::AsRawFd,
+    },
     path::{Path, PathBuf},
-    sync::Arc,
+    sync::{Arc, Mutex},
     time::Instant,
 };
 
-use bytes::BytesMut;
+use bytes::Bytes;
 use clap::Parser;
+use io_uring::{opcode, types, IoUring};
 use ndarray::{Array3, ArrayView3, Axis, Slice};
+use nix::fcntl::{fallocate, FallocateFlags};
 use rand::RngCore;
 use zarrs::{
     array::{Array, ArrayBuilder, FillValue},
@@ -29,6 +35,75 @@ use nix::libc::O_DIRECT;
 const CHUNK: usize = 16;
 const SIDE: u64 = 512;
 const SHAPE: [u64; 3] = [65536, SIDE, SIDE];
+/// The declared fill value of the array, shared by every writer so elided/punched chunks
+/// and the verification in `Compare` agree on what "fill" means.
+const FILL_VALUE: u16 = 7;
+
+/// O_DIRECT does not guarantee data or metadata is durable on the device; this selects
+/// when/how writers make chunk files durable.
+#[derive(Default, Clone, Debug, clap::ValueEnum)]
+enum DurabilityMode {
+    /// Do not explicitly flush; rely on whatever the OS gets around to.
+    #[default]
+    None,
+    /// `fdatasync` after every chunk file.
+    PerChunk,
+    /// `fsync` every `--durability-batch-n` chunk files.
+    Batched,
+    /// A single `syncfs` of the target filesystem once all chunks are written.
+    Syncfs,
+}
+
+/// Tracks a writer's durability policy and how much time it spends making data durable,
+/// kept separate from the raw write time it's layered on top of.
+struct Durability {
+    mode: DurabilityMode,
+    batch_n: usize,
+    since_last_sync: usize,
+    durable_time: std::time::Duration,
+}
+
+impl Durability {
+    fn new(mode: DurabilityMode, batch_n: usize) -> Self {
+        Self {
+            mode,
+            batch_n,
+            since_last_sync: 0,
+            durable_time: std::time::Duration::ZERO,
+        }
+    }
+
+    /// Called after a chunk file has been written, to apply `per-chunk`/`batched` policy.
+    fn after_write(&mut self, key_path: &Path) {
+        match self.mode {
+            DurabilityMode::None | DurabilityMode::Syncfs => {}
+            DurabilityMode::PerChunk => {
+                let t0 = Instant::now();
+                std::fs::File::open(key_path).unwrap().sync_data().unwrap();
+                self.durable_time += t0.elapsed();
+            }
+            DurabilityMode::Batched => {
+                self.since_last_sync += 1;
+                if self.since_last_sync >= self.batch_n {
+                    let t0 = Instant::now();
+                    std::fs::File::open(key_path).unwrap().sync_all().unwrap();
+                    self.durable_time += t0.elapsed();
+                    self.since_last_sync = 0;
+                }
+            }
+        }
+    }
+
+    /// Called once after the write loop completes, to apply `syncfs` policy.
+    fn finish(&mut self, save_path: &Path) {
+        if let DurabilityMode::Syncfs = self.mode {
+            let t0 = Instant::now();
+            let f = std::fs::File::open(save_path).unwrap();
+            nix::unistd::syncfs(f.as_raw_fd()).unwrap();
+            self.durable_time += t0.elapsed();
+        }
+    }
+}
 
 /// Write the array using the built-in `FilesystemStore` of `zarrs`
 fn write_buffered_io(save_path: &Path, array_path: &str, input_data: &ArrayView3<u16>) {
@@ -39,7 +114,7 @@ fn write_buffered_io(save_path: &Path, array_path: &str, input_data: &ArrayView3
         SHAPE.to_vec(),
         zarrs::array::DataType::UInt16,
         chunk_grid.try_into().unwrap(),
-        FillValue::from(7u16),
+        FillValue::from(FILL_VALUE),
     )
     .dimension_names(["i", "Ky", "Kx"].into())
     .build(Arc::clone(&store), array_path)
@@ -59,7 +134,12 @@ fn write_buffered_io(save_path: &Path, array_path: &str, input_data: &ArrayView3
 }
 
 /// Write the array using the built-in `FilesystemStore` of `zarrs` with `direct_io` enabled.
-fn write_direct_zarrs(save_path: &Path, array_path: &str, input_data: &ArrayView3<u16>) {
+fn write_direct_zarrs(
+    save_path: &Path,
+    array_path: &str,
+    input_data: &ArrayView3<u16>,
+    durability: &mut Durability,
+) {
     let mut opts = FilesystemStoreOptions::default();
     opts.direct_io(true);
     let store: ReadableWritableListableStorage =
@@ -70,7 +150,7 @@ fn write_direct_zarrs(save_path: &Path, array_path: &str, input_data: &ArrayView
         SHAPE.to_vec(),
         zarrs::array::DataType::UInt16,
         chunk_grid.try_into().unwrap(),
-        FillValue::from(7u16),
+        FillValue::from(FILL_VALUE),
     )
     .dimension_names(["i", "Ky", "Kx"].into())
     .build(Arc::clone(&store), array_path)
@@ -80,26 +160,178 @@ fn write_direct_zarrs(save_path: &Path, array_path: &str, input_data: &ArrayView
     let t0 = Instant::now();
 
     for i in 0..(65536 / CHUNK as u64) {
+        let chunk_indices = [i, 0, 0];
         let inp_slice = input_data.slice_axis(Axis(0), Slice::from(i as usize..i as usize + CHUNK));
         array
-            .store_chunk_elements(&[i, 0, 0], inp_slice.as_slice().unwrap())
+            .store_chunk_elements(&chunk_indices, inp_slice.as_slice().unwrap())
             .unwrap();
+
+        let key = data_key(array.path(), &chunk_indices, array.chunk_key_encoding());
+        durability.after_write(&key_to_fspath(save_path, &key));
     }
+    durability.finish(save_path);
 
-    eprintln!("write_direct_zarrs took {:?}", t0.elapsed());
+    let total = t0.elapsed();
+    eprintln!(
+        "write_direct_zarrs took {:?} (raw write {:?}, durability {:?})",
+        total,
+        total - durability.durable_time,
+        durability.durable_time,
+    );
 }
 
-/// For O_DIRECT, we need a buffer that is aligned to the page size and is a
-/// multiple of the page size.
-fn bytes_aligned(size: usize) -> BytesMut {
-    let align = page_size::get();
-    let mut bytes = BytesMut::with_capacity(size + align * 2);
-    let offset = bytes.as_ptr().align_offset(align);
-    bytes.split_off(offset)
+/// A buffer allocated directly via `alloc`/`Layout::from_size_align`, guaranteeing both
+/// page alignment and a page-multiple size, suitable for O_DIRECT reads and writes.
+struct AlignedBuf {
+    ptr: std::ptr::NonNull<u8>,
+    layout: std::alloc::Layout,
+    len: usize,
+}
+
+impl AlignedBuf {
+    /// Allocate a zeroed buffer with capacity for at least `size` bytes, rounded up to the
+    /// next page multiple.
+    fn new(size: usize) -> Self {
+        let align = page_size::get();
+        let capacity = size.next_multiple_of(align).max(align);
+        let layout = std::alloc::Layout::from_size_align(capacity, align).unwrap();
+        let ptr = unsafe { std::alloc::alloc_zeroed(layout) };
+        let ptr = std::ptr::NonNull::new(ptr).unwrap_or_else(|| std::alloc::handle_alloc_error(layout));
+        Self { ptr, layout, len: 0 }
+    }
+
+    fn capacity(&self) -> usize {
+        self.layout.size()
+    }
+
+    fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    fn extend_from_slice(&mut self, data: &[u8]) {
+        assert!(self.len + data.len() <= self.capacity());
+        unsafe {
+            std::ptr::copy_nonoverlapping(data.as_ptr(), self.ptr.as_ptr().add(self.len), data.len());
+        }
+        self.len += data.len();
+    }
+
+    /// The bytes written so far.
+    fn as_slice(&self) -> &[u8] {
+        unsafe { std::slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+
+    /// The full, page-aligned, page-multiple-sized backing storage (including any padding
+    /// past `len`: zeroes in a fresh buffer, earlier bytes after a `clear`), ready to hand
+    /// to an O_DIRECT write.
+    fn as_padded_slice(&self) -> &[u8] {
+        unsafe { std::slice::from_raw_parts(self.ptr.as_ptr(), self.capacity()) }
+    }
+
+    /// The full, page-aligned, page-multiple-sized backing storage as a mutable slice,
+    /// ready to receive an O_DIRECT read.
+    fn as_mut_padded_slice(&mut self) -> &mut [u8] {
+        unsafe { std::slice::from_raw_parts_mut(self.ptr.as_ptr(), self.capacity()) }
+    }
+
+    /// Allocate a new, larger `AlignedBuf` holding this buffer's bytes followed by `extra`.
+    fn with_appended(&self, extra: &[u8]) -> AlignedBuf {
+        let mut grown = AlignedBuf::new(self.len + extra.len());
+        grown.extend_from_slice(self.as_slice());
+        grown.extend_from_slice(extra);
+        grown
+    }
+}
+
+impl AsRef<[u8]> for AlignedBuf {
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+// The buffer owns its allocation outright and never exposes shared mutable aliasing
+// across threads, so it's safe to move between (and share a read-only view across)
+// threads; this lets it be wrapped in a `Bytes::from_owner` without copying.
+unsafe impl Send for AlignedBuf {}
+unsafe impl Sync for AlignedBuf {}
+
+impl Drop for AlignedBuf {
+    fn drop(&mut self) {
+        unsafe {
+            std::alloc::dealloc(self.ptr.as_ptr(), self.layout);
+        }
+    }
+}
+
+/// A small fixed-depth pool of reusable [`AlignedBuf`]s, so a writer can hand one buffer
+/// off to the store while immediately acquiring the next, keeping several aligned buffers
+/// in flight without repeated alloc/dealloc.
+///
+/// Buffers handed out via [`acquire`](Self::acquire) come back wrapped in a [`PooledBuf`],
+/// which returns them to `free` on drop rather than deallocating them — so a buffer goes
+/// back into circulation once whatever holds the `PooledBuf` (e.g. a `Bytes::from_owner`)
+/// is truly done with it, not just when the caller's local variable goes out of scope.
+#[derive(Clone)]
+struct AlignedBufPool {
+    buf_size: usize,
+    free: Arc<Mutex<Vec<AlignedBuf>>>,
+}
+
+impl AlignedBufPool {
+    fn new(depth: usize, buf_size: usize) -> Self {
+        let free = (0..depth).map(|_| AlignedBuf::new(buf_size)).collect();
+        Self { buf_size, free: Arc::new(Mutex::new(free)) }
+    }
+
+    fn acquire(&self) -> PooledBuf {
+        let buf = self.free.lock().unwrap().pop().unwrap_or_else(|| AlignedBuf::new(self.buf_size));
+        PooledBuf { buf: ManuallyDrop::new(buf), free: Arc::clone(&self.free) }
+    }
+}
+
+/// An [`AlignedBuf`] on loan from an [`AlignedBufPool`]. Transparently derefs to the
+/// underlying buffer; when dropped (including when dropped by `Bytes` after the store
+/// releases its last reference), the buffer is cleared and pushed back onto the pool's
+/// free list instead of being deallocated.
+struct PooledBuf {
+    buf: ManuallyDrop<AlignedBuf>,
+    free: Arc<Mutex<Vec<AlignedBuf>>>,
+}
+
+impl Deref for PooledBuf {
+    type Target = AlignedBuf;
+    fn deref(&self) -> &AlignedBuf {
+        &self.buf
+    }
+}
+
+impl DerefMut for PooledBuf {
+    fn deref_mut(&mut self) -> &mut AlignedBuf {
+        &mut self.buf
+    }
+}
+
+impl AsRef<[u8]> for PooledBuf {
+    fn as_ref(&self) -> &[u8] {
+        self.buf.as_slice()
+    }
+}
+
+impl Drop for PooledBuf {
+    fn drop(&mut self) {
+        let mut buf = unsafe { ManuallyDrop::take(&mut self.buf) };
+        buf.clear();
+        self.free.lock().unwrap().push(buf);
+    }
 }
 
 /// Write the array using the built-in `FilesystemStore` of `zarrs` with `direct_io` enabled.
-fn write_direct_zarrs_manual_encode(save_path: &Path, array_path: &str, input_data: &ArrayView3<u16>) {
+fn write_direct_zarrs_manual_encode(
+    save_path: &Path,
+    array_path: &str,
+    input_data: &ArrayView3<u16>,
+    durability: &mut Durability,
+) {
     let mut opts = FilesystemStoreOptions::default();
     opts.direct_io(true);
     let store: ReadableWritableListableStorage =
@@ -110,7 +342,7 @@ fn write_direct_zarrs_manual_encode(save_path: &Path, array_path: &str, input_da
         SHAPE.to_vec(),
         zarrs::array::DataType::UInt16,
         chunk_grid.try_into().unwrap(),
-        FillValue::from(7u16),
+        FillValue::from(FILL_VALUE),
     )
     .dimension_names(["i", "Ky", "Kx"].into())
     .build(Arc::clone(&store), array_path)
@@ -119,28 +351,39 @@ fn write_direct_zarrs_manual_encode(save_path: &Path, array_path: &str, input_da
 
     let t0 = Instant::now();
 
-    let mut buf: BytesMut = bytes_aligned(CHUNK *(SIDE * SIDE * 2) as usize);
+    let chunk_bytes = CHUNK * (SIDE * SIDE * 2) as usize;
+    let pool = AlignedBufPool::new(2, chunk_bytes);
 
     for i in 0..(65536 / CHUNK as u64) {
-        assert!(buf.as_ptr().align_offset(page_size::get()) == 0, "a");
+        let chunk_indices = [i, 0, 0];
+        let mut buf = pool.acquire();
+        buf.clear();
 
         let inp_slice = input_data.slice_axis(Axis(0), Slice::from(i as usize..i as usize + CHUNK));
-        buf.clear();
-        assert!(buf.as_ptr().align_offset(page_size::get()) == 0, "a.0");
         buf.extend_from_slice(inp_slice.as_slice().unwrap().as_bytes());
 
-        assert!(buf.as_ptr().align_offset(page_size::get()) == 0, "b");
-
-        let buf_frozen = buf.freeze();
+        // Hand the aligned buffer itself to the store: copying into a fresh `Bytes` (as
+        // this used to) loses the page alignment O_DIRECT requires and fails the write
+        // with EINVAL. `from_owner` wraps the buffer without copying, and `PooledBuf`'s
+        // `Drop` returns it to `pool.free` once the store's last reference goes away —
+        // so acquiring a fresh one from the pool each iteration doesn't leak allocations.
+        let encoded = Bytes::from_owner(buf);
         unsafe {
-            array
-            .store_encoded_chunk(&[i, 0, 0], buf_frozen.clone())
-                .unwrap();
+            array.store_encoded_chunk(&chunk_indices, encoded).unwrap();
         }
-        buf = buf_frozen.try_into_mut().unwrap();  // FIXME: handle the case where the buffer is still in use
+
+        let key = data_key(array.path(), &chunk_indices, array.chunk_key_encoding());
+        durability.after_write(&key_to_fspath(save_path, &key));
     }
+    durability.finish(save_path);
 
-    eprintln!("write_direct_zarrs_manual_encode took {:?}", t0.elapsed());
+    let total = t0.elapsed();
+    eprintln!(
+        "write_direct_zarrs_manual_encode took {:?} (raw write {:?}, durability {:?})",
+        total,
+        total - durability.durable_time,
+        durability.durable_time,
+    );
 }
 
 fn key_to_fspath(save_path: &Path, key: &StoreKey) -> PathBuf {
@@ -151,8 +394,299 @@ fn key_to_fspath(save_path: &Path, key: &StoreKey) -> PathBuf {
     path
 }
 
+/// Budget for the userspace write-back cache used by [`write_direct_io_cached`].
+const USERSPACE_CACHE_BYTES: usize = 64 * 1024 * 1024;
+
+/// A single page-aligned cache slot holding pending, not-yet-written bytes for one file.
+struct CacheSlot {
+    path: PathBuf,
+    file_offset: u64,
+    buf: AlignedBuf,
+    dirty: bool,
+}
+
+impl CacheSlot {
+    /// Write this slot back to `path` at `file_offset`; the backing `AlignedBuf` is already
+    /// page-multiple sized, so the write stays aligned without extra padding.
+    ///
+    /// `touched` tracks which paths have already been written back once during this run.
+    /// Only the first writeback for a given path truncates it on open (to drop any stale
+    /// trailing bytes left over from a previous run); later writebacks for the same path —
+    /// e.g. a second, non-adjacent slot staged after the first was evicted — must not
+    /// re-truncate, or they'd destroy the first slot's already-written bytes. After the
+    /// padded write, `set_len` cuts the file back to the larger of its previous length and
+    /// this slot's extent, dropping the O_DIRECT alignment padding without cutting off
+    /// bytes written earlier.
+    fn writeback(&self, touched: &mut std::collections::HashSet<PathBuf>) {
+        if !self.dirty {
+            return;
+        }
+
+        let real_len = self.file_offset + self.buf.as_slice().len() as u64;
+        let first_write = touched.insert(self.path.clone());
+
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(first_write)
+            .custom_flags(O_DIRECT)
+            .open(&self.path)
+            .unwrap();
+
+        // Every writeback trims its own padding, so this is where earlier bytes end.
+        let prev_len = file.metadata().unwrap().len();
+
+        file.seek(SeekFrom::Start(self.file_offset)).unwrap();
+        file.write_all(self.buf.as_padded_slice()).unwrap();
+
+        file.set_len(prev_len.max(real_len)).unwrap();
+    }
+}
+
+/// A bounded, LRU-evicted userspace write-back cache sitting between
+/// `store_chunk_elements`/`store_encoded_chunk` and the filesystem. Incoming chunk
+/// byte-ranges are staged into page-aligned slots; a slot that is adjacent to an
+/// already-cached dirty slot for the same file is coalesced into it, so eviction and
+/// flush write back in page-multiple extents rather than one `pwrite` per chunk.
+struct WritebackCache {
+    budget_bytes: usize,
+    used_bytes: usize,
+    /// Least-recently-used slot first.
+    slots: Vec<CacheSlot>,
+    /// Paths that have already received their one truncating writeback this run; see
+    /// [`CacheSlot::writeback`].
+    touched_paths: std::collections::HashSet<PathBuf>,
+}
+
+impl WritebackCache {
+    fn new(budget_bytes: usize) -> Self {
+        Self {
+            budget_bytes,
+            used_bytes: 0,
+            slots: Vec::new(),
+            touched_paths: std::collections::HashSet::new(),
+        }
+    }
+
+    /// Stage `data` at `file_offset` within `path`. Data that continues a cached slot of the
+    /// same file is appended to that slot without evicting anything; otherwise LRU slots are
+    /// evicted until `data` fits within the budget.
+    fn stage(&mut self, path: &Path, file_offset: u64, data: &[u8]) {
+        if let Some(pos) = self
+            .slots
+            .iter()
+            .position(|s| s.path == path && s.file_offset + s.buf.as_slice().len() as u64 == file_offset)
+        {
+            let slot = self.slots.remove(pos);
+            self.used_bytes -= slot.buf.capacity();
+            let grown = slot.buf.with_appended(data);
+            self.used_bytes += grown.capacity();
+            self.slots.push(CacheSlot {
+                path: slot.path,
+                file_offset: slot.file_offset,
+                buf: grown,
+                dirty: true,
+            });
+            return;
+        }
+
+        while !self.slots.is_empty() && self.used_bytes + data.len() > self.budget_bytes {
+            self.evict_lru();
+        }
+
+        let mut buf = AlignedBuf::new(data.len());
+        buf.extend_from_slice(data);
+        self.used_bytes += buf.capacity();
+        self.slots.push(CacheSlot {
+            path: path.to_owned(),
+            file_offset,
+            buf,
+            dirty: true,
+        });
+    }
+
+    fn evict_lru(&mut self) {
+        let slot = self.slots.remove(0);
+        slot.writeback(&mut self.touched_paths);
+        self.used_bytes -= slot.buf.capacity();
+    }
+
+    /// Write back and drop all cached slots.
+    fn flush(&mut self) {
+        let touched_paths = &mut self.touched_paths;
+        for slot in self.slots.drain(..) {
+            slot.writeback(touched_paths);
+        }
+        self.used_bytes = 0;
+    }
+}
+
+impl Drop for WritebackCache {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+/// A storage wrapper around another `ReadableWritableListableStorage` that fronts every
+/// `set`/`set_partial_values` call with a [`WritebackCache`], so it sits exactly where the
+/// request asked: between `store_chunk_elements`/`store_encoded_chunk` and the filesystem.
+/// Reads, listing, and erasure pass straight through to the wrapped store; only the write
+/// path is cached and coalesced.
+struct WritebackStore {
+    inner: ReadableWritableListableStorage,
+    root: PathBuf,
+    cache: std::sync::Mutex<WritebackCache>,
+}
+
+impl WritebackStore {
+    fn new(inner: ReadableWritableListableStorage, root: PathBuf, budget_bytes: usize) -> Self {
+        Self {
+            inner,
+            root,
+            cache: std::sync::Mutex::new(WritebackCache::new(budget_bytes)),
+        }
+    }
+
+    /// Write back and drop every cached slot.
+    fn flush(&self) {
+        self.cache.lock().unwrap().flush();
+    }
+}
+
+impl zarrs::storage::ReadableStorageTraits for WritebackStore {
+    fn get(&self, key: &StoreKey) -> Result<zarrs::storage::MaybeBytes, zarrs::storage::StorageError> {
+        self.inner.get(key)
+    }
+
+    fn get_partial_values_key(
+        &self,
+        key: &StoreKey,
+        byte_ranges: &[zarrs::storage::ByteRange],
+    ) -> Result<Option<Vec<Bytes>>, zarrs::storage::StorageError> {
+        self.inner.get_partial_values_key(key, byte_ranges)
+    }
+
+    fn get_partial_values(
+        &self,
+        key_ranges: &[zarrs::storage::StoreKeyRange],
+    ) -> Result<Vec<zarrs::storage::MaybeBytes>, zarrs::storage::StorageError> {
+        self.inner.get_partial_values(key_ranges)
+    }
+
+    fn size_key(&self, key: &StoreKey) -> Result<Option<u64>, zarrs::storage::StorageError> {
+        self.inner.size_key(key)
+    }
+}
+
+impl zarrs::storage::WritableStorageTraits for WritebackStore {
+    fn set(&self, key: &StoreKey, value: Bytes) -> Result<(), zarrs::storage::StorageError> {
+        let path = key_to_fspath(&self.root, key);
+        if let Some(parent) = path.parent() {
+            if !parent.exists() {
+                std::fs::create_dir_all(parent).unwrap();
+            }
+        }
+        self.cache.lock().unwrap().stage(&path, 0, &value);
+        Ok(())
+    }
+
+    fn set_partial_values(
+        &self,
+        key_start_values: &[zarrs::storage::StoreKeyStartValue],
+    ) -> Result<(), zarrs::storage::StorageError> {
+        for key_start_value in key_start_values {
+            let path = key_to_fspath(&self.root, key_start_value.key());
+            self.cache
+                .lock()
+                .unwrap()
+                .stage(&path, key_start_value.start(), key_start_value.value());
+        }
+        Ok(())
+    }
+
+    fn erase(&self, key: &StoreKey) -> Result<(), zarrs::storage::StorageError> {
+        self.inner.erase(key)
+    }
+
+    fn erase_prefix(&self, prefix: &zarrs::storage::StorePrefix) -> Result<(), zarrs::storage::StorageError> {
+        self.inner.erase_prefix(prefix)
+    }
+}
+
+impl zarrs::storage::ListableStorageTraits for WritebackStore {
+    fn list(&self) -> Result<zarrs::storage::StoreKeys, zarrs::storage::StorageError> {
+        self.inner.list()
+    }
+
+    fn list_prefix(
+        &self,
+        prefix: &zarrs::storage::StorePrefix,
+    ) -> Result<zarrs::storage::StoreKeys, zarrs::storage::StorageError> {
+        self.inner.list_prefix(prefix)
+    }
+
+    fn list_dir(
+        &self,
+        prefix: &zarrs::storage::StorePrefix,
+    ) -> Result<zarrs::storage::StoreKeysPrefixes, zarrs::storage::StorageError> {
+        self.inner.list_dir(prefix)
+    }
+
+    fn size_prefix(&self, prefix: &zarrs::storage::StorePrefix) -> Result<u64, zarrs::storage::StorageError> {
+        self.inner.size_prefix(prefix)
+    }
+}
+
+/// Write the array through a [`WritebackStore`], so that small or partial chunk writes get
+/// coalesced into larger aligned `pwrite`s instead of paying a full read-modify-write per
+/// chunk. Going through the real storage trait (rather than writing chunk files by hand)
+/// means `array.store_chunk_elements` writes through the cache; reads go to the wrapped
+/// store and see only bytes that have been written back.
+fn write_direct_io_cached(save_path: &Path, array_path: &str, input_data: &ArrayView3<u16>) {
+    let inner: ReadableWritableListableStorage = Arc::new(FilesystemStore::new(save_path).unwrap());
+    let writeback = Arc::new(WritebackStore::new(
+        inner,
+        save_path.to_owned(),
+        USERSPACE_CACHE_BYTES,
+    ));
+    let store: ReadableWritableListableStorage = writeback.clone();
+    let chunk_grid = vec![CHUNK as u64, SIDE, SIDE];
+
+    let array = ArrayBuilder::new(
+        SHAPE.to_vec(),
+        zarrs::array::DataType::UInt16,
+        chunk_grid.try_into().unwrap(),
+        FillValue::from(FILL_VALUE),
+    )
+    .dimension_names(["i", "Ky", "Kx"].into())
+    .build(Arc::clone(&store), array_path)
+    .unwrap();
+    array.store_metadata().unwrap();
+
+    let t0 = Instant::now();
+
+    for i in 0..(65536 / CHUNK as u64) {
+        let inp_slice = input_data.slice_axis(Axis(0), Slice::from(i as usize..i as usize + CHUNK));
+        let chunk_indices = [i, 0, 0];
+        array
+            .store_chunk_elements(&chunk_indices, inp_slice.as_slice().unwrap())
+            .unwrap();
+    }
+
+    writeback.flush();
+
+    eprintln!("write_direct_io_cached took {:?}", t0.elapsed());
+}
+
 /// Write the array using a fast-path O_DIRECT writer
-fn write_direct_io(save_path: &Path, array_path: &str, input_data: &ArrayView3<u16>) {
+fn write_direct_io(
+    save_path: &Path,
+    array_path: &str,
+    input_data: &ArrayView3<u16>,
+    elide_fill_chunks: bool,
+    preallocate: bool,
+    durability: &mut Durability,
+) {
     let store: ReadableWritableListableStorage = Arc::new(FilesystemStore::new(save_path).unwrap());
     let chunk_grid = vec![CHUNK as u64, SIDE, SIDE];
 
@@ -160,7 +694,7 @@ fn write_direct_io(save_path: &Path, array_path: &str, input_data: &ArrayView3<u
         SHAPE.to_vec(),
         zarrs::array::DataType::UInt16,
         chunk_grid.try_into().unwrap(),
-        FillValue::from(7u16),
+        FillValue::from(FILL_VALUE),
     )
     .dimension_names(["i", "Ky", "Kx"].into())
     .build(Arc::clone(&store), array_path)
@@ -169,7 +703,7 @@ fn write_direct_io(save_path: &Path, array_path: &str, input_data: &ArrayView3<u
 
     let t0 = Instant::now();
 
-    let mut buf = bytes_aligned((SIDE * SIDE * 2) as usize * CHUNK);
+    let mut buf = AlignedBuf::new((SIDE * SIDE * 2) as usize * CHUNK);
 
     for i in 0..(65536 / CHUNK as u64) {
         let inp_slice = input_data.slice_axis(Axis(0), Slice::from(i as usize..i as usize + CHUNK));
@@ -186,32 +720,560 @@ fn write_direct_io(save_path: &Path, array_path: &str, input_data: &ArrayView3<u
             }
         }
 
-        let mut file = OpenOptions::new()
+        let file = OpenOptions::new()
             .write(true)
             .create(true)
             .truncate(true)
             .custom_flags(O_DIRECT)
-            .open(key_path)
+            .open(&key_path)
             .unwrap();
 
         // Only write as much as we have to
         let cutoff = SIDE * SIDE * CHUNK as u64 * size_of::<u16>() as u64;
 
-        // Copy into aligned buffer:
+        // Each chunk here is its own file, so we fallocate it to its final length right
+        // after opening (rather than once for a single backing file) to get a contiguous
+        // extent before the O_DIRECT write.
+        if preallocate {
+            fallocate(file.as_raw_fd(), FallocateFlags::empty(), 0, cutoff as i64).unwrap();
+        }
+
+        if elide_fill_chunks && data.iter().all(|&v| v == FILL_VALUE) {
+            // Leave this chunk as a hole instead of writing it: fill-value chunks need no
+            // bytes on disk, and punching keeps the file sparse even if it was preallocated.
+            file.set_len(cutoff).unwrap();
+            fallocate(
+                file.as_raw_fd(),
+                FallocateFlags::FALLOC_FL_PUNCH_HOLE | FallocateFlags::FALLOC_FL_KEEP_SIZE,
+                0,
+                cutoff as i64,
+            )
+            .unwrap();
+            durability.after_write(&key_path);
+            continue;
+        }
+
+        // Copy into aligned buffer; the backing allocation is already page-multiple sized.
         buf.clear();
-        let data_bytes = data.as_bytes();
-        let pad_size = data_bytes.len().next_multiple_of(page_size::get()) - data_bytes.len();
-        buf.extend_from_slice(data_bytes);
-        buf.extend(std::iter::repeat(0).take(pad_size));
+        buf.extend_from_slice(data.as_bytes());
 
         // Write
-        file.write_all(&buf).unwrap();
+        (&file).write_all(buf.as_padded_slice()).unwrap();
 
         // We may have written more because of page-size alignment; truncate.
         file.set_len(cutoff).unwrap();
+
+        durability.after_write(&key_path);
     }
+    durability.finish(save_path);
+
+    let total = t0.elapsed();
+    eprintln!(
+        "write_direct_io took {:?} (raw write {:?}, durability {:?})",
+        total,
+        total - durability.durable_time,
+        durability.durable_time,
+    );
+}
 
-    eprintln!("write_direct_io took {:?}", t0.elapsed());
+/// Reinterpret page-aligned bytes read off disk as `u16` elements. `AlignedBuf`'s page
+/// alignment is more than sufficient alignment for `u16`, so this is a plain reinterpret
+/// with no copy.
+fn u16s_from_aligned_bytes(bytes: &[u8]) -> &[u16] {
+    unsafe { std::slice::from_raw_parts(bytes.as_ptr() as *const u16, bytes.len() / size_of::<u16>()) }
+}
+
+/// Read the array back using a fast-path O_DIRECT reader: each chunk file is opened with
+/// `O_DIRECT` and read via a positioned read into a single reused page-aligned buffer,
+/// substituting the fill value for chunks that were elided as holes (see
+/// [`write_direct_io`]'s `elide_fill_chunks`). Used both for the `direct_read` benchmark and
+/// for verification in `Compare`.
+fn read_direct_io(save_path: &Path, array_path: &str) -> Array3<u16> {
+    let store: ReadableWritableListableStorage = Arc::new(FilesystemStore::new(save_path).unwrap());
+    let array = Array::open(store, array_path).unwrap();
+
+    let t0 = Instant::now();
+
+    let chunk_elements = CHUNK * (SIDE * SIDE) as usize;
+    let chunk_bytes = chunk_elements * size_of::<u16>();
+    let mut buf = AlignedBuf::new(chunk_bytes);
+    let mut data = vec![0u16; 65536 * (SIDE * SIDE) as usize];
+
+    for i in 0..(65536 / CHUNK as u64) {
+        let chunk_indices = [i, 0, 0];
+        let key = data_key(array.path(), &chunk_indices, array.chunk_key_encoding());
+        let key_path = key_to_fspath(save_path, &key);
+
+        let row = &mut data[i as usize * chunk_elements..(i as usize + 1) * chunk_elements];
+
+        // A missing file, or one with no allocated blocks, is an elided fill-value chunk.
+        let is_hole = std::fs::metadata(&key_path).map_or(true, |meta| meta.blocks() == 0);
+        if is_hole {
+            row.fill(FILL_VALUE);
+            continue;
+        }
+
+        let file = OpenOptions::new()
+            .read(true)
+            .custom_flags(O_DIRECT)
+            .open(&key_path)
+            .unwrap();
+        file.read_exact_at(buf.as_mut_padded_slice(), 0).unwrap();
+
+        row.copy_from_slice(&u16s_from_aligned_bytes(buf.as_padded_slice())[..chunk_elements]);
+    }
+
+    eprintln!("read_direct_io took {:?}", t0.elapsed());
+
+    Array3::from_shape_vec([65536, SIDE as usize, SIDE as usize], data).unwrap()
+}
+
+/// Directory (relative to an array's root) holding the content-addressed blobs written by
+/// [`DedupStore`].
+const DEDUP_BLOB_DIR: &str = "_blobs";
+
+/// Mutable bookkeeping behind [`DedupStore`]'s `Mutex`: which store key maps to which
+/// content hash, and how many of each hash's referents exist (for GC).
+struct DedupState {
+    refcounts: std::collections::HashMap<String, u64>,
+    manifest: std::collections::HashMap<String, String>,
+    chunks_written: usize,
+    chunks_total: usize,
+}
+
+/// A storage wrapper that deduplicates identical payloads (e.g. repeated dark frames or
+/// blanked regions in chunk data, but this applies to any key including array metadata).
+/// Each distinct payload is written to the blob directory once, keyed by its blake3 hash;
+/// a manifest then maps every store key to the hash of its content, with a refcount per
+/// hash for GC. Because this implements the same storage trait surface as `FilesystemStore`,
+/// the normal zarrs read path (`Array::open`/`retrieve_array_subset`) resolves straight
+/// through it, rather than needing its own ad hoc reader.
+struct DedupStore {
+    inner: ReadableWritableListableStorage,
+    root: PathBuf,
+    blob_dir: PathBuf,
+    state: std::sync::Mutex<DedupState>,
+}
+
+impl DedupStore {
+    /// Start a fresh dedup store rooted at `root` (an array's directory), falling back to
+    /// `inner` for anything not yet written through this store.
+    fn new(inner: ReadableWritableListableStorage, root: PathBuf) -> Self {
+        let blob_dir = root.join(DEDUP_BLOB_DIR);
+        std::fs::create_dir_all(&blob_dir).unwrap();
+        Self {
+            inner,
+            root,
+            blob_dir,
+            state: std::sync::Mutex::new(DedupState {
+                refcounts: std::collections::HashMap::new(),
+                manifest: std::collections::HashMap::new(),
+                chunks_written: 0,
+                chunks_total: 0,
+            }),
+        }
+    }
+
+    /// Load a previously-written dedup store's manifest, for the read path.
+    fn load(inner: ReadableWritableListableStorage, root: PathBuf) -> Self {
+        let blob_dir = root.join(DEDUP_BLOB_DIR);
+        let mut manifest = std::collections::HashMap::new();
+        let mut refcounts = std::collections::HashMap::new();
+
+        let contents = std::fs::read_to_string(root.join("manifest.txt")).unwrap_or_default();
+        for line in contents.lines() {
+            let mut parts = line.split_whitespace();
+            let key = parts.next().unwrap().to_string();
+            let hash = parts.next().unwrap().to_string();
+            *refcounts.entry(hash.clone()).or_insert(0) += 1;
+            manifest.insert(key, hash);
+        }
+
+        let chunks_total = manifest.len();
+        let chunks_written = refcounts.len();
+        Self {
+            inner,
+            root,
+            blob_dir,
+            state: std::sync::Mutex::new(DedupState {
+                refcounts,
+                manifest,
+                chunks_written,
+                chunks_total,
+            }),
+        }
+    }
+
+    fn blob_path(&self, hash: &str) -> PathBuf {
+        self.blob_dir.join(hash)
+    }
+
+    /// Record `data` as the content of `key`, writing it to the blob store only if this
+    /// exact content hasn't been seen before; otherwise just bumps the refcount.
+    fn store(&self, key: &str, data: &[u8]) {
+        let hash = blake3::hash(data).to_hex().to_string();
+        let mut state = self.state.lock().unwrap();
+
+        state.chunks_total += 1;
+
+        if let Some(count) = state.refcounts.get_mut(&hash) {
+            *count += 1;
+        } else {
+            let mut buf = AlignedBuf::new(data.len());
+            buf.extend_from_slice(data);
+
+            let file = OpenOptions::new()
+                .write(true)
+                .create(true)
+                .truncate(true)
+                .custom_flags(O_DIRECT)
+                .open(self.blob_path(&hash))
+                .unwrap();
+            (&file).write_all(buf.as_padded_slice()).unwrap();
+            file.set_len(data.len() as u64).unwrap();
+
+            state.refcounts.insert(hash.clone(), 1);
+            state.chunks_written += 1;
+        }
+
+        state.manifest.insert(key.to_string(), hash);
+    }
+
+    /// Persist the manifest so a later process can resolve keys back to blobs.
+    fn save_manifest(&self) {
+        let state = self.state.lock().unwrap();
+        let mut out = String::new();
+        for (key, hash) in &state.manifest {
+            out.push_str(&format!("{key} {hash}\n"));
+        }
+        std::fs::write(self.root.join("manifest.txt"), out).unwrap();
+    }
+
+    /// Number of distinct payloads actually written to the blob directory.
+    fn chunks_written(&self) -> usize {
+        self.state.lock().unwrap().chunks_written
+    }
+
+    /// Number of writes recorded through this store (for a store opened with
+    /// [`load`](Self::load), the number of keys in its manifest).
+    fn chunks_total(&self) -> usize {
+        self.state.lock().unwrap().chunks_total
+    }
+
+    /// Fraction of writes that landed a new blob on disk rather than reusing one (1.0 = no
+    /// dedup, near 0.0 = highly repetitive data).
+    fn dedup_ratio(&self) -> f64 {
+        let state = self.state.lock().unwrap();
+        if state.chunks_total > 0 {
+            state.chunks_written as f64 / state.chunks_total as f64
+        } else {
+            1.0
+        }
+    }
+}
+
+impl zarrs::storage::ReadableStorageTraits for DedupStore {
+    fn get(&self, key: &StoreKey) -> Result<zarrs::storage::MaybeBytes, zarrs::storage::StorageError> {
+        let hash = self.state.lock().unwrap().manifest.get(key.as_str()).cloned();
+        match hash {
+            Some(hash) => Ok(Some(Bytes::from(std::fs::read(self.blob_path(&hash)).unwrap()))),
+            None => self.inner.get(key),
+        }
+    }
+
+    fn get_partial_values_key(
+        &self,
+        key: &StoreKey,
+        byte_ranges: &[zarrs::storage::ByteRange],
+    ) -> Result<Option<Vec<Bytes>>, zarrs::storage::StorageError> {
+        self.inner.get_partial_values_key(key, byte_ranges)
+    }
+
+    fn get_partial_values(
+        &self,
+        key_ranges: &[zarrs::storage::StoreKeyRange],
+    ) -> Result<Vec<zarrs::storage::MaybeBytes>, zarrs::storage::StorageError> {
+        self.inner.get_partial_values(key_ranges)
+    }
+
+    fn size_key(&self, key: &StoreKey) -> Result<Option<u64>, zarrs::storage::StorageError> {
+        self.inner.size_key(key)
+    }
+}
+
+impl zarrs::storage::WritableStorageTraits for DedupStore {
+    fn set(&self, key: &StoreKey, value: Bytes) -> Result<(), zarrs::storage::StorageError> {
+        self.store(key.as_str(), &value);
+        Ok(())
+    }
+
+    fn set_partial_values(
+        &self,
+        key_start_values: &[zarrs::storage::StoreKeyStartValue],
+    ) -> Result<(), zarrs::storage::StorageError> {
+        self.inner.set_partial_values(key_start_values)
+    }
+
+    fn erase(&self, key: &StoreKey) -> Result<(), zarrs::storage::StorageError> {
+        self.inner.erase(key)
+    }
+
+    fn erase_prefix(&self, prefix: &zarrs::storage::StorePrefix) -> Result<(), zarrs::storage::StorageError> {
+        self.inner.erase_prefix(prefix)
+    }
+}
+
+impl zarrs::storage::ListableStorageTraits for DedupStore {
+    fn list(&self) -> Result<zarrs::storage::StoreKeys, zarrs::storage::StorageError> {
+        self.inner.list()
+    }
+
+    fn list_prefix(
+        &self,
+        prefix: &zarrs::storage::StorePrefix,
+    ) -> Result<zarrs::storage::StoreKeys, zarrs::storage::StorageError> {
+        self.inner.list_prefix(prefix)
+    }
+
+    fn list_dir(
+        &self,
+        prefix: &zarrs::storage::StorePrefix,
+    ) -> Result<zarrs::storage::StoreKeysPrefixes, zarrs::storage::StorageError> {
+        self.inner.list_dir(prefix)
+    }
+
+    fn size_prefix(&self, prefix: &zarrs::storage::StorePrefix) -> Result<u64, zarrs::storage::StorageError> {
+        self.inner.size_prefix(prefix)
+    }
+}
+
+/// Write the array through a [`DedupStore`] wrapped around a `FilesystemStore`: identical
+/// payloads (chunk data and metadata alike) are written to disk only once, with a manifest
+/// recording which store keys share which content hash. Because this is a real storage
+/// wrapper, `array.store_chunk_elements` works exactly as it does for any other store, and
+/// so does the standard read path.
+fn write_direct_io_dedup(save_path: &Path, array_path: &str, input_data: &ArrayView3<u16>) {
+    let inner: ReadableWritableListableStorage = Arc::new(FilesystemStore::new(save_path).unwrap());
+    let root = save_path.join(array_path.trim_start_matches('/'));
+    let dedup = Arc::new(DedupStore::new(inner, root));
+    let store: ReadableWritableListableStorage = dedup.clone();
+    let chunk_grid = vec![CHUNK as u64, SIDE, SIDE];
+
+    let array = ArrayBuilder::new(
+        SHAPE.to_vec(),
+        zarrs::array::DataType::UInt16,
+        chunk_grid.try_into().unwrap(),
+        FillValue::from(FILL_VALUE),
+    )
+    .dimension_names(["i", "Ky", "Kx"].into())
+    .build(Arc::clone(&store), array_path)
+    .unwrap();
+    array.store_metadata().unwrap();
+
+    let t0 = Instant::now();
+
+    for i in 0..(65536 / CHUNK as u64) {
+        let inp_slice = input_data.slice_axis(Axis(0), Slice::from(i as usize..i as usize + CHUNK));
+        let chunk_indices = [i, 0, 0];
+        array
+            .store_chunk_elements(&chunk_indices, inp_slice.as_slice().unwrap())
+            .unwrap();
+    }
+
+    dedup.save_manifest();
+
+    eprintln!(
+        "write_direct_io_dedup took {:?} ({}/{} writes landed a new blob, dedup ratio {:.3})",
+        t0.elapsed(),
+        dedup.chunks_written(),
+        dedup.chunks_total(),
+        dedup.dedup_ratio(),
+    );
+}
+
+/// Submission queue depth, and number of fixed buffers registered, for
+/// [`write_direct_io_uring`].
+const IO_URING_QUEUE_DEPTH: usize = 32;
+
+/// Wait for at least one completion, returning its buffer slot to the free pool and
+/// dropping (closing) the file its write just landed on, since `files` is indexed by the
+/// fixed buffer slot and gets overwritten the next time that slot is reused.
+fn reap_io_uring_completions(
+    ring: &mut IoUring,
+    free_slots: &mut Vec<usize>,
+    in_flight: &mut usize,
+    files: &mut [Option<std::fs::File>],
+) {
+    ring.submit_and_wait(1).unwrap();
+    let cqes: Vec<_> = ring.completion().collect();
+    for cqe in cqes {
+        assert!(cqe.result() >= 0, "io_uring write failed: {}", cqe.result());
+        let slot = cqe.user_data() as usize;
+        files[slot] = None;
+        free_slots.push(slot);
+        *in_flight -= 1;
+    }
+}
+
+/// Write the array via io_uring, keeping many aligned chunk writes in flight instead of
+/// serializing on each O_DIRECT completion: a pool of registered fixed buffers is filled
+/// and submitted as `IORING_OP_WRITE_FIXED` SQEs, back-pressuring once all buffers are busy.
+fn write_direct_io_uring(save_path: &Path, array_path: &str, input_data: &ArrayView3<u16>) {
+    let store: ReadableWritableListableStorage = Arc::new(FilesystemStore::new(save_path).unwrap());
+    let chunk_grid = vec![CHUNK as u64, SIDE, SIDE];
+
+    let array = ArrayBuilder::new(
+        SHAPE.to_vec(),
+        zarrs::array::DataType::UInt16,
+        chunk_grid.try_into().unwrap(),
+        FillValue::from(FILL_VALUE),
+    )
+    .dimension_names(["i", "Ky", "Kx"].into())
+    .build(Arc::clone(&store), array_path)
+    .unwrap();
+    array.store_metadata().unwrap();
+
+    let t0 = Instant::now();
+
+    let depth = IO_URING_QUEUE_DEPTH;
+    let chunk_bytes = (SIDE * SIDE * 2) as usize * CHUNK;
+
+    let mut ring = IoUring::new(depth as u32).unwrap();
+
+    let mut buffers: Vec<AlignedBuf> = (0..depth).map(|_| AlignedBuf::new(chunk_bytes)).collect();
+    let iovecs: Vec<nix::libc::iovec> = buffers
+        .iter()
+        .map(|b| nix::libc::iovec {
+            iov_base: b.as_padded_slice().as_ptr() as *mut _,
+            iov_len: b.capacity(),
+        })
+        .collect();
+    unsafe {
+        ring.submitter().register_buffers(&iovecs).unwrap();
+    }
+
+    // One open file per in-flight buffer slot, kept alive until its write completes.
+    let mut files: Vec<Option<std::fs::File>> = (0..depth).map(|_| None).collect();
+    let mut free_slots: Vec<usize> = (0..depth).collect();
+    let mut in_flight = 0usize;
+
+    for i in 0..(65536 / CHUNK as u64) {
+        while free_slots.is_empty() {
+            reap_io_uring_completions(&mut ring, &mut free_slots, &mut in_flight, &mut files);
+        }
+        let slot = free_slots.pop().unwrap();
+
+        let inp_slice = input_data.slice_axis(Axis(0), Slice::from(i as usize..i as usize + CHUNK));
+        let chunk_indices = [i, 0, 0];
+        let data = inp_slice.as_slice().unwrap();
+        let key = data_key(array.path(), &chunk_indices, array.chunk_key_encoding());
+
+        let key_path = key_to_fspath(save_path, &key);
+        if let Some(parent) = key_path.parent() {
+            if !parent.exists() {
+                std::fs::create_dir_all(parent).unwrap();
+            }
+        }
+
+        let file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .custom_flags(O_DIRECT)
+            .open(&key_path)
+            .unwrap();
+        file.set_len(chunk_bytes as u64).unwrap();
+
+        buffers[slot].clear();
+        buffers[slot].extend_from_slice(data.as_bytes());
+
+        let write_e = opcode::WriteFixed::new(
+            types::Fd(file.as_raw_fd()),
+            buffers[slot].as_padded_slice().as_ptr(),
+            buffers[slot].capacity() as u32,
+            slot as u16,
+        )
+        .build()
+        .user_data(slot as u64);
+
+        unsafe {
+            ring.submission().push(&write_e).unwrap();
+        }
+        files[slot] = Some(file);
+        in_flight += 1;
+
+        if ring.submission().len() >= depth {
+            ring.submit().unwrap();
+        }
+    }
+
+    ring.submit().unwrap();
+    while in_flight > 0 {
+        reap_io_uring_completions(&mut ring, &mut free_slots, &mut in_flight, &mut files);
+    }
+
+    eprintln!("write_direct_io_uring took {:?}", t0.elapsed());
+}
+
+/// Default number of timed iterations `--what benchmark` runs per mode; untimed `--warmup`
+/// iterations come first and aren't counted.
+const ITERATIONS: usize = 5;
+
+/// Render a byte count using binary (KiB/MiB/GiB) units for human-readable benchmark output.
+fn human_readable_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.2} {}", UNITS[unit])
+}
+
+/// Timing summary for one mode's run through [`run_benchmark`].
+struct BenchResult {
+    mode: &'static str,
+    min: std::time::Duration,
+    median: std::time::Duration,
+    max: std::time::Duration,
+    mb_per_sec: f64,
+}
+
+/// Run `run_once` `warmup` untimed times, then `iterations` timed times, reporting
+/// min/median/max latency and the MB/s implied by `payload_bytes` (the known size of the
+/// data moved per iteration) over the median.
+fn run_benchmark(
+    mode: &'static str,
+    warmup: usize,
+    iterations: usize,
+    payload_bytes: u64,
+    mut run_once: impl FnMut(),
+) -> BenchResult {
+    for _ in 0..warmup {
+        run_once();
+    }
+
+    let mut durations: Vec<std::time::Duration> = (0..iterations)
+        .map(|_| {
+            let t0 = Instant::now();
+            run_once();
+            t0.elapsed()
+        })
+        .collect();
+    durations.sort();
+
+    let min = durations[0];
+    let max = durations[durations.len() - 1];
+    let median = durations[durations.len() / 2];
+    let mb_per_sec = (payload_bytes as f64 / (1024.0 * 1024.0)) / median.as_secs_f64();
+
+    eprintln!(
+        "{mode}: min {min:?}, median {median:?}, max {max:?}, {mb_per_sec:.1} MB/s ({} payload, {iterations} iterations)",
+        human_readable_bytes(payload_bytes),
+    );
+
+    BenchResult { mode, min, median, max, mb_per_sec }
 }
 
 #[derive(Default, Clone, Debug, clap::ValueEnum)]
@@ -221,6 +1283,11 @@ enum RunWhat {
     Buffered,
     DirectZarrs,
     DirectZarrsEncoded,
+    DirectCached,
+    DirectDedup,
+    DirectIoUring,
+    DirectRead,
+    Benchmark,
     #[default]
     Direct,
 }
@@ -234,10 +1301,37 @@ struct Args {
 
     #[arg(short, long)]
     random: bool,
+
+    /// Skip writing chunks that are entirely the fill value, punching a hole instead.
+    #[arg(long)]
+    elide_fill_chunks: bool,
+
+    /// Preallocate each chunk file to its final length via `fallocate` before writing.
+    #[arg(long)]
+    preallocate: bool,
+
+    /// How/when written chunk files are made durable.
+    #[arg(long, value_enum, default_value = "none")]
+    durability: DurabilityMode,
+
+    /// Number of chunk files between `fsync` calls in `batched` durability mode.
+    #[arg(long, default_value_t = 16)]
+    durability_batch_n: usize,
+
+    /// Number of timed iterations `--what benchmark` runs per mode.
+    #[arg(long, default_value_t = ITERATIONS)]
+    iterations: usize,
+
+    /// Number of untimed warmup iterations `--what benchmark` runs per mode before timing.
+    #[arg(long, default_value_t = 1)]
+    warmup: usize,
 }
 
 fn make_data(random: bool) -> Array3<u16> {
-    let mut data = vec![0u16; 65536 * (SIDE * SIDE) as usize];
+    // Match the array's declared fill value, not an arbitrary `0`, so that `--elide-fill-
+    // chunks` (and its `Compare` verification) actually have fill-value chunks to elide
+    // when `--random` isn't set.
+    let mut data = vec![FILL_VALUE; 65536 * (SIDE * SIDE) as usize];
     let data_bytes = data.as_bytes_mut();
 
     if random {
@@ -256,26 +1350,151 @@ fn main() {
         RunWhat::All => {
             let input_arr = make_data(args.random);
             write_buffered_io(&args.save_prefix, "/buffered", &input_arr.view());
-            write_direct_io(&args.save_prefix, "/direct", &input_arr.view());
-            write_direct_zarrs_manual_encode(&args.save_prefix, "/direct_zarrs_encoded", &input_arr.view());
-            write_direct_zarrs(&args.save_prefix, "/direct_zarrs", &input_arr.view());
+            write_direct_io(
+                &args.save_prefix,
+                "/direct",
+                &input_arr.view(),
+                args.elide_fill_chunks,
+                args.preallocate,
+                &mut Durability::new(args.durability.clone(), args.durability_batch_n),
+            );
+            write_direct_io_cached(&args.save_prefix, "/direct_cached", &input_arr.view());
+            write_direct_io_dedup(&args.save_prefix, "/direct_dedup", &input_arr.view());
+            write_direct_io_uring(&args.save_prefix, "/direct_io_uring", &input_arr.view());
+            write_direct_zarrs_manual_encode(
+                &args.save_prefix,
+                "/direct_zarrs_encoded",
+                &input_arr.view(),
+                &mut Durability::new(args.durability.clone(), args.durability_batch_n),
+            );
+            write_direct_zarrs(
+                &args.save_prefix,
+                "/direct_zarrs",
+                &input_arr.view(),
+                &mut Durability::new(args.durability.clone(), args.durability_batch_n),
+            );
         }
         RunWhat::Direct => {
             let input_arr = make_data(args.random);
-            write_direct_io(&args.save_prefix, "/direct", &input_arr.view());
+            write_direct_io(
+                &args.save_prefix,
+                "/direct",
+                &input_arr.view(),
+                args.elide_fill_chunks,
+                args.preallocate,
+                &mut Durability::new(args.durability.clone(), args.durability_batch_n),
+            );
+        }
+        RunWhat::DirectCached => {
+            let input_arr = make_data(args.random);
+            write_direct_io_cached(&args.save_prefix, "/direct_cached", &input_arr.view());
+        }
+        RunWhat::DirectDedup => {
+            let input_arr = make_data(args.random);
+            write_direct_io_dedup(&args.save_prefix, "/direct_dedup", &input_arr.view());
+        }
+        RunWhat::DirectIoUring => {
+            let input_arr = make_data(args.random);
+            write_direct_io_uring(&args.save_prefix, "/direct_io_uring", &input_arr.view());
+        }
+        RunWhat::DirectRead => {
+            read_direct_io(&args.save_prefix, "/direct");
         }
         RunWhat::DirectZarrs => {
             let input_arr = make_data(args.random);
-            write_direct_zarrs(&args.save_prefix, "/direct_zarrs", &input_arr.view());
+            write_direct_zarrs(
+                &args.save_prefix,
+                "/direct_zarrs",
+                &input_arr.view(),
+                &mut Durability::new(args.durability.clone(), args.durability_batch_n),
+            );
         }
         RunWhat::DirectZarrsEncoded => {
             let input_arr = make_data(args.random);
-            write_direct_zarrs_manual_encode(&args.save_prefix, "/direct_zarrs_encoded", &input_arr.view());
+            write_direct_zarrs_manual_encode(
+                &args.save_prefix,
+                "/direct_zarrs_encoded",
+                &input_arr.view(),
+                &mut Durability::new(args.durability.clone(), args.durability_batch_n),
+            );
         }
         RunWhat::Buffered => {
             let input_arr = make_data(args.random);
             write_buffered_io(&args.save_prefix, "/buffered", &input_arr.view());
         }
+        RunWhat::Benchmark => {
+            let input_arr = make_data(args.random);
+            let payload_bytes = 65536 * SIDE * SIDE * size_of::<u16>() as u64;
+            let mut results = Vec::new();
+
+            results.push(run_benchmark("buffered", args.warmup, args.iterations, payload_bytes, || {
+                write_buffered_io(&args.save_prefix, "/buffered", &input_arr.view());
+            }));
+            results.push(run_benchmark("direct", args.warmup, args.iterations, payload_bytes, || {
+                write_direct_io(
+                    &args.save_prefix,
+                    "/direct",
+                    &input_arr.view(),
+                    args.elide_fill_chunks,
+                    args.preallocate,
+                    &mut Durability::new(args.durability.clone(), args.durability_batch_n),
+                );
+            }));
+            results.push(run_benchmark("direct_cached", args.warmup, args.iterations, payload_bytes, || {
+                write_direct_io_cached(&args.save_prefix, "/direct_cached", &input_arr.view());
+            }));
+            results.push(run_benchmark(
+                "direct_zarrs",
+                args.warmup,
+                args.iterations,
+                payload_bytes,
+                || {
+                    write_direct_zarrs(
+                        &args.save_prefix,
+                        "/direct_zarrs",
+                        &input_arr.view(),
+                        &mut Durability::new(args.durability.clone(), args.durability_batch_n),
+                    );
+                },
+            ));
+            results.push(run_benchmark(
+                "direct_zarrs_encoded",
+                args.warmup,
+                args.iterations,
+                payload_bytes,
+                || {
+                    write_direct_zarrs_manual_encode(
+                        &args.save_prefix,
+                        "/direct_zarrs_encoded",
+                        &input_arr.view(),
+                        &mut Durability::new(args.durability.clone(), args.durability_batch_n),
+                    );
+                },
+            ));
+            results.push(run_benchmark(
+                "direct_io_uring",
+                args.warmup,
+                args.iterations,
+                payload_bytes,
+                || {
+                    write_direct_io_uring(&args.save_prefix, "/direct_io_uring", &input_arr.view());
+                },
+            ));
+            results.push(run_benchmark("direct_read", args.warmup, args.iterations, payload_bytes, || {
+                read_direct_io(&args.save_prefix, "/direct");
+            }));
+
+            eprintln!(
+                "\n{:<22} {:>12} {:>12} {:>12} {:>10}",
+                "mode", "min", "median", "max", "MB/s"
+            );
+            for r in &results {
+                eprintln!(
+                    "{:<22} {:>12.3?} {:>12.3?} {:>12.3?} {:>10.1}",
+                    r.mode, r.min, r.median, r.max, r.mb_per_sec
+                );
+            }
+        }
         RunWhat::Compare => {
             let store: ReadableWritableListableStorage =
                 Arc::new(FilesystemStore::new(&args.save_prefix).unwrap());
@@ -283,6 +1502,8 @@ fn main() {
             let a_dir = Array::open(Arc::clone(&store), "/direct").unwrap();
             let a_dir_z = Array::open(Arc::clone(&store), "/direct_zarrs").unwrap();
             let a_dir_ze = Array::open(Arc::clone(&store), "/direct_zarrs_encoded").unwrap();
+            let a_io_uring = Array::open(Arc::clone(&store), "/direct_io_uring").ok();
+            let a_cached = Array::open(Arc::clone(&store), "/direct_cached").ok();
 
             let read_chunk = 1;
             for i in 0..(65536 / read_chunk) {
@@ -298,6 +1519,76 @@ fn main() {
                 assert_eq!(buf_bytes, dir_bytes);
                 assert_eq!(buf_bytes, dir_z_bytes);
                 assert_eq!(buf_bytes, dir_ze_bytes);
+                if let Some(a_io_uring) = &a_io_uring {
+                    assert_eq!(buf_bytes, a_io_uring.retrieve_array_subset(&subset).unwrap());
+                }
+                if let Some(a_cached) = &a_cached {
+                    assert_eq!(buf_bytes, a_cached.retrieve_array_subset(&subset).unwrap());
+                }
+            }
+
+            if args.elide_fill_chunks {
+                for i in 0..(65536 / CHUNK as u64) {
+                    let chunk_indices = [i, 0, 0];
+                    let key = data_key(a_dir.path(), &chunk_indices, a_dir.chunk_key_encoding());
+                    let key_path = key_to_fspath(&args.save_prefix, &key);
+
+                    // A chunk file with no allocated blocks is entirely a punched hole.
+                    let is_hole = std::fs::metadata(&key_path).map_or(true, |meta| meta.blocks() == 0);
+                    if !is_hole {
+                        continue;
+                    }
+
+                    let subset = ArraySubset::new_with_ranges(&[
+                        i * CHUNK as u64..i * CHUNK as u64 + CHUNK as u64,
+                        0..SIDE,
+                        0..SIDE,
+                    ]);
+                    let elements = a_dir.retrieve_array_subset_elements::<u16>(&subset).unwrap();
+                    assert!(
+                        elements.iter().all(|&v| v == FILL_VALUE),
+                        "punched chunk {i} did not read back as the fill value"
+                    );
+                }
+            }
+
+            let direct_read = read_direct_io(&args.save_prefix, "/direct");
+            for i in 0..(65536 / CHUNK as u64) {
+                let subset = ArraySubset::new_with_ranges(&[
+                    i * CHUNK as u64..i * CHUNK as u64 + CHUNK as u64,
+                    0..SIDE,
+                    0..SIDE,
+                ]);
+                let buf_elements = a_buf.retrieve_array_subset_elements::<u16>(&subset).unwrap();
+                let row_start = i as usize * CHUNK * (SIDE * SIDE) as usize;
+                let row_len = CHUNK * (SIDE * SIDE) as usize;
+                assert_eq!(buf_elements, direct_read.as_slice().unwrap()[row_start..row_start + row_len]);
+            }
+
+            let dedup_root = args.save_prefix.join("direct_dedup");
+            if dedup_root.join("manifest.txt").exists() {
+                let dedup_inner: ReadableWritableListableStorage =
+                    Arc::new(FilesystemStore::new(&args.save_prefix).unwrap());
+                let dedup = Arc::new(DedupStore::load(dedup_inner, dedup_root));
+                let dedup_store: ReadableWritableListableStorage = dedup.clone();
+                let a_dedup = Array::open(dedup_store, "/direct_dedup").unwrap();
+
+                for i in 0..(65536 / CHUNK as u64) {
+                    let subset = ArraySubset::new_with_ranges(&[
+                        i * CHUNK as u64..i * CHUNK as u64 + CHUNK as u64,
+                        0..SIDE,
+                        0..SIDE,
+                    ]);
+                    let buf_bytes = a_buf.retrieve_array_subset(&subset).unwrap();
+                    let dedup_bytes = a_dedup.retrieve_array_subset(&subset).unwrap();
+                    assert_eq!(buf_bytes, dedup_bytes);
+                }
+                eprintln!(
+                    "direct_dedup: {} distinct blobs out of {} writes (dedup ratio {:.3})",
+                    dedup.chunks_written(),
+                    dedup.chunks_total(),
+                    dedup.dedup_ratio(),
+                );
             }
         }
     }